@@ -0,0 +1,156 @@
+//! An in-memory, indexed collection of [`ScancodeLicense`]s.
+
+use std::collections::HashMap;
+
+use crate::models::ScancodeLicense;
+
+/// An in-memory container of [`ScancodeLicense`]s that indexes by `key` and by SPDX identifier
+/// at construction time, mirroring how the SPDX license list separates `licenses` from
+/// `exceptions`.
+pub struct ScancodeLicenseDb {
+    licenses: Vec<ScancodeLicense>,
+    by_key: HashMap<String, usize>,
+    by_spdx: HashMap<String, usize>,
+}
+
+impl ScancodeLicenseDb {
+    /// Build a database from `licenses`, indexing each by its `key` and by its
+    /// `spdx_license_key`/`other_spdx_license_keys`.
+    #[must_use]
+    pub fn new(licenses: Vec<ScancodeLicense>) -> Self {
+        let mut by_key = HashMap::with_capacity(licenses.len());
+        let mut by_spdx = HashMap::new();
+
+        for (index, license) in licenses.iter().enumerate() {
+            by_key.insert(license.key.clone(), index);
+
+            for spdx_key in license.spdx_license_key.iter().chain(&license.other_spdx_license_keys) {
+                by_spdx.insert(spdx_key.clone(), index);
+            }
+        }
+
+        Self {
+            licenses,
+            by_key,
+            by_spdx,
+        }
+    }
+
+    /// Look up a license by its ScanCode `key`.
+    #[must_use]
+    pub fn by_key(&self, key: &str) -> Option<&ScancodeLicense> {
+        self.by_key.get(key).map(|&index| &self.licenses[index])
+    }
+
+    /// Look up a license by an SPDX identifier, whether it's the primary `spdx_license_key` or
+    /// one of `other_spdx_license_keys`.
+    #[must_use]
+    pub fn by_spdx(&self, spdx_key: &str) -> Option<&ScancodeLicense> {
+        self.by_spdx
+            .get(spdx_key)
+            .map(|&index| &self.licenses[index])
+    }
+
+    /// Iterate over every license and exception in the database.
+    pub fn iter(&self) -> impl Iterator<Item = &ScancodeLicense> {
+        self.licenses.iter()
+    }
+
+    /// Number of licenses and exceptions in the database.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.licenses.len()
+    }
+
+    /// Whether the database holds no licenses.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.licenses.is_empty()
+    }
+
+    /// Entries marked `is_exception`, as opposed to full licenses.
+    pub fn exceptions(&self) -> impl Iterator<Item = &ScancodeLicense> {
+        self.licenses.iter().filter(|license| license.is_exception)
+    }
+
+    /// Entries marked `is_deprecated`.
+    pub fn deprecated(&self) -> impl Iterator<Item = &ScancodeLicense> {
+        self.licenses.iter().filter(|license| license.is_deprecated)
+    }
+}
+
+impl<'a> IntoIterator for &'a ScancodeLicenseDb {
+    type Item = &'a ScancodeLicense;
+    type IntoIter = std::slice::Iter<'a, ScancodeLicense>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.licenses.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::ScancodeLicense;
+
+    use super::*;
+
+    fn test_db() -> ScancodeLicenseDb {
+        let mut mit = ScancodeLicense::test_fixture("mit", "");
+        mit.spdx_license_key = Some("MIT".to_string());
+
+        let mut gpl = ScancodeLicense::test_fixture("gpl-3.0", "");
+        gpl.spdx_license_key = Some("GPL-3.0".to_string());
+        gpl.other_spdx_license_keys = vec!["GPL-3.0-only".to_string()];
+
+        let mut exception = ScancodeLicense::test_fixture("llvm-exception", "");
+        exception.is_exception = true;
+
+        let mut deprecated = ScancodeLicense::test_fixture("old-license", "");
+        deprecated.is_deprecated = true;
+
+        ScancodeLicenseDb::new(vec![mit, gpl, exception, deprecated])
+    }
+
+    #[test]
+    fn looks_up_by_key() {
+        let db = test_db();
+
+        assert_eq!(db.by_key("mit").unwrap().key, "mit");
+        assert!(db.by_key("does-not-exist").is_none());
+    }
+
+    #[test]
+    fn looks_up_by_primary_and_other_spdx_keys() {
+        let db = test_db();
+
+        assert_eq!(db.by_spdx("MIT").unwrap().key, "mit");
+        assert_eq!(db.by_spdx("GPL-3.0").unwrap().key, "gpl-3.0");
+        assert_eq!(db.by_spdx("GPL-3.0-only").unwrap().key, "gpl-3.0");
+        assert!(db.by_spdx("does-not-exist").is_none());
+    }
+
+    #[test]
+    fn partitions_exceptions_and_deprecated() {
+        let db = test_db();
+
+        assert_eq!(db.len(), 4);
+        assert!(!db.is_empty());
+
+        let exceptions: Vec<&str> = db.exceptions().map(|license| license.key.as_str()).collect();
+        assert_eq!(exceptions, vec!["llvm-exception"]);
+
+        let deprecated: Vec<&str> = db.deprecated().map(|license| license.key.as_str()).collect();
+        assert_eq!(deprecated, vec!["old-license"]);
+    }
+
+    #[test]
+    fn iterates_over_every_license() {
+        let db = test_db();
+
+        let keys: Vec<&str> = db.iter().map(|license| license.key.as_str()).collect();
+        assert_eq!(keys.len(), 4);
+
+        let keys_via_into_iter: Vec<&str> = (&db).into_iter().map(|license| license.key.as_str()).collect();
+        assert_eq!(keys, keys_via_into_iter);
+    }
+}
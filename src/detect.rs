@@ -0,0 +1,263 @@
+//! License text detection against arbitrary file contents.
+//!
+//! This builds an index over the [`text`](crate::models::ScancodeLicense::text) of every loaded
+//! license and lets callers ask which licenses a given piece of text (a `LICENSE` file, or a
+//! comment header copied out of a source file) most likely contains.
+
+use std::collections::HashMap;
+
+use crate::models::ScancodeLicense;
+
+/// Coverage score (0-100) required for a match when the license does not set its own
+/// [`minimum_coverage`](crate::models::ScancodeLicense::minimum_coverage).
+const DEFAULT_MINIMUM_COVERAGE: f64 = 50.0;
+
+/// A multiset of token bigrams, with the total bigram count cached alongside it so the
+/// Sørensen–Dice coefficient doesn't need to recompute it on every comparison.
+struct BigramMultiset {
+    counts: HashMap<(String, String), usize>,
+    size: usize,
+}
+
+impl BigramMultiset {
+    fn from_tokens(tokens: &[String]) -> Self {
+        let mut counts: HashMap<(String, String), usize> = HashMap::new();
+
+        for pair in tokens.windows(2) {
+            *counts
+                .entry((pair[0].clone(), pair[1].clone()))
+                .or_insert(0) += 1;
+        }
+
+        let size = counts.values().sum();
+
+        Self { counts, size }
+    }
+
+    /// Sørensen–Dice coefficient `2*|A∩B| / (|A|+|B|)` against another multiset, scaled to 0-100.
+    fn dice_coefficient(&self, other: &Self) -> f64 {
+        if self.size == 0 || other.size == 0 {
+            return 0.0;
+        }
+
+        let intersection: usize = self
+            .counts
+            .iter()
+            .map(|(bigram, count)| (*count).min(*other.counts.get(bigram).unwrap_or(&0)))
+            .sum();
+
+        (2.0 * intersection as f64 / (self.size + other.size) as f64) * 100.0
+    }
+}
+
+/// Lowercase `text`, collapse all whitespace runs to a single space, and drop tokens made up
+/// entirely of punctuation, yielding the token sequence used for bigram comparison.
+fn tokenize(text: &str) -> Vec<String> {
+    text.split_whitespace()
+        .map(str::to_lowercase)
+        .filter(|token| token.chars().any(char::is_alphanumeric))
+        .collect()
+}
+
+/// Remove every occurrence of each `needle` from `haystack`, case-insensitively.
+///
+/// Works entirely in lowercased text: searching a lowercased needle in the original-case
+/// haystack and then slicing the original by the needle's own byte length would go wrong as
+/// soon as lowercasing changes a byte length or offset (e.g. `İ` lowercases to two bytes more
+/// than its uppercase form), which can panic on a non-char boundary or strip the wrong bytes.
+fn strip_ignorable(haystack: &str, needles: &[String]) -> String {
+    let mut result = haystack.to_lowercase();
+
+    for needle in needles {
+        let needle = needle.to_lowercase();
+
+        if needle.is_empty() {
+            continue;
+        }
+
+        while let Some(start) = result.find(&needle) {
+            result.replace_range(start..start + needle.len(), " ");
+        }
+    }
+
+    result
+}
+
+/// A single license match produced by [`LicenseIndex::detect`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Match {
+    /// Key of the matched [`ScancodeLicense`].
+    pub key: String,
+
+    /// Coverage score in 0-100, the Sørensen–Dice coefficient between the input and the
+    /// license's text.
+    pub coverage: f64,
+}
+
+/// A license together with the pieces needed to detect it in arbitrary text, precomputed once so
+/// [`LicenseIndex::detect`] doesn't re-normalize every license's text on every call.
+struct IndexedLicense {
+    key: String,
+    minimum_coverage: Option<i32>,
+    ignorable: Vec<String>,
+    bigrams: BigramMultiset,
+}
+
+/// An index over a set of licenses' texts, built once and reused across [`detect`](Self::detect)
+/// calls.
+pub struct LicenseIndex {
+    licenses: Vec<IndexedLicense>,
+}
+
+impl LicenseIndex {
+    /// Build an index over `licenses`, normalizing and caching each one's token bigrams.
+    #[must_use]
+    pub fn new(licenses: &[ScancodeLicense]) -> Self {
+        let indexed = licenses
+            .iter()
+            .map(|license| {
+                let ignorable: Vec<String> = license
+                    .ignorable_copyrights
+                    .iter()
+                    .chain(&license.ignorable_holders)
+                    .chain(&license.ignorable_urls)
+                    .chain(&license.ignorable_emails)
+                    .cloned()
+                    .collect();
+
+                let normalized = strip_ignorable(&license.text, &ignorable);
+
+                IndexedLicense {
+                    key: license.key.clone(),
+                    minimum_coverage: license.minimum_coverage,
+                    bigrams: BigramMultiset::from_tokens(&tokenize(&normalized)),
+                    ignorable,
+                }
+            })
+            .collect();
+
+        Self { licenses: indexed }
+    }
+
+    /// Find which indexed licenses `input` most likely contains, sorted by descending coverage.
+    ///
+    /// A license is only returned if its coverage score meets its own
+    /// [`minimum_coverage`](crate::models::ScancodeLicense::minimum_coverage), or
+    /// [`DEFAULT_MINIMUM_COVERAGE`] when it doesn't set one.
+    #[must_use]
+    pub fn detect(&self, input: &str) -> Vec<Match> {
+        let mut matches: Vec<Match> = self
+            .licenses
+            .iter()
+            .filter_map(|license| {
+                let normalized = strip_ignorable(input, &license.ignorable);
+                let input_bigrams = BigramMultiset::from_tokens(&tokenize(&normalized));
+                let coverage = input_bigrams.dice_coefficient(&license.bigrams);
+
+                let threshold = license
+                    .minimum_coverage
+                    .map_or(DEFAULT_MINIMUM_COVERAGE, f64::from);
+
+                (coverage >= threshold).then(|| Match {
+                    key: license.key.clone(),
+                    coverage,
+                })
+            })
+            .collect();
+
+        matches.sort_by(|a, b| b.coverage.total_cmp(&a.coverage));
+
+        matches
+    }
+}
+
+/// Find which of `licenses` `input` most likely contains. Prefer building a [`LicenseIndex`]
+/// directly and reusing it when calling this more than once, since that avoids re-normalizing
+/// every license's text on each call.
+#[must_use]
+pub fn detect(licenses: &[ScancodeLicense], input: &str) -> Vec<Match> {
+    LicenseIndex::new(licenses).detect(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::ScancodeLicense;
+
+    use super::*;
+
+    #[test]
+    fn detects_an_exact_match() {
+        let license = ScancodeLicense::test_fixture(
+            "mit",
+            "Permission is hereby granted, free of charge, to any person obtaining a copy.",
+        );
+        let index = LicenseIndex::new(&[license]);
+
+        let matches = index.detect(
+            "Permission is hereby granted, free of charge, to any person obtaining a copy.",
+        );
+
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].key, "mit");
+        assert!((matches[0].coverage - 100.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn does_not_match_unrelated_text() {
+        let license = ScancodeLicense::test_fixture(
+            "mit",
+            "Permission is hereby granted, free of charge, to any person obtaining a copy.",
+        );
+        let index = LicenseIndex::new(&[license]);
+
+        let matches = index.detect("This is a README describing an unrelated Rust crate.");
+
+        assert!(matches.is_empty());
+    }
+
+    #[test]
+    fn ignores_ignorable_holder_text_on_both_sides() {
+        let mut license = ScancodeLicense::test_fixture(
+            "mit",
+            "Copyright John Doe. Permission is hereby granted to use this software.",
+        );
+        license.ignorable_holders = vec!["John Doe".to_string()];
+        let index = LicenseIndex::new(&[license]);
+
+        let matches =
+            index.detect("Copyright Jane Smith. Permission is hereby granted to use this software.");
+
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].key, "mit");
+    }
+
+    #[test]
+    fn filters_below_minimum_coverage() {
+        let mut license = ScancodeLicense::test_fixture(
+            "mit",
+            "Permission is hereby granted, free of charge, to any person obtaining a copy.",
+        );
+        license.minimum_coverage = Some(100);
+        let index = LicenseIndex::new(&[license]);
+
+        let matches = index.detect("Permission is hereby granted, free of charge.");
+
+        assert!(matches.is_empty());
+    }
+
+    #[test]
+    fn empty_ignorable_entries_do_not_hang() {
+        let mut license = ScancodeLicense::test_fixture(
+            "mit",
+            "Permission is hereby granted, free of charge, to any person obtaining a copy.",
+        );
+        license.ignorable_holders = vec![String::new()];
+        let index = LicenseIndex::new(&[license]);
+
+        let matches = index.detect(
+            "Permission is hereby granted, free of charge, to any person obtaining a copy.",
+        );
+
+        assert_eq!(matches.len(), 1);
+    }
+}
@@ -1,18 +1,209 @@
 use std::ffi::OsStr;
-use std::fs::read_dir;
-use std::path::Path;
+use std::fs::{create_dir_all, read, read_dir, write};
+use std::path::{Path, PathBuf};
 
+use directories::ProjectDirs;
 use git2::Repository;
+use serde::Deserialize;
 use tempfile::tempdir;
 
+use crate::db::ScancodeLicenseDb;
 use crate::models::Result;
 use crate::models::ScancodeError;
 use crate::models::ScancodeLicense;
 
+/// Raw content host used to fetch individual files out of the license database without cloning
+/// the whole repository.
+const RAW_BASE_URL: &str = "https://raw.githubusercontent.com/nexB/scancode-licensedb";
+
+/// Cache key used for [`from_scancode_database`], which always clones the repository's default
+/// branch rather than a pinned ref.
+const DEFAULT_DATABASE_REF: &str = "default";
+
 /// Get all licenses from the [ScanCode license database](https://github.com/nexB/scancode-licensedb).
+///
+/// Reuses a previously fetched set from the [`cache_dir`] when one exists, so repeated calls
+/// (and builds or CI runs without network access) don't need to re-clone the repository every
+/// time. Call [`from_git_database`] directly to always fetch fresh.
 pub fn from_scancode_database() -> Result<Vec<ScancodeLicense>> {
+    let cache_path = cache_dir().map(|dir| dir.join(format!("{DEFAULT_DATABASE_REF}.bin")));
+
+    if let Some(path) = &cache_path {
+        if let Ok(licenses) = from_cache(path) {
+            return Ok(licenses);
+        }
+    }
+
     let licenses = from_git_database("https://github.com/nexB/scancode-licensedb.git", "docs")?;
 
+    if let Some(path) = &cache_path {
+        store_to_cache(&licenses, path)?;
+    }
+
+    Ok(licenses)
+}
+
+/// Like [`from_scancode_database`], but returns a [`ScancodeLicenseDb`] so callers can resolve
+/// keys and SPDX identifiers in O(1) instead of linear-scanning a `Vec`.
+pub fn from_scancode_database_db() -> Result<ScancodeLicenseDb> {
+    Ok(ScancodeLicenseDb::new(from_scancode_database()?))
+}
+
+/// Get all licenses from the [ScanCode license database](https://github.com/nexB/scancode-licensedb)
+/// over HTTPS, without cloning the repository.
+///
+/// This reads the `index.yml` manifest and then fetches each referenced `.yml`/`.LICENSE` pair
+/// individually, which is far cheaper than [`from_scancode_database`] for callers that only need
+/// the license data and not the repository history. `version` pins a tag, branch or commit of
+/// `scancode-licensedb`; `None` resolves the repository's actual default branch via the GitHub
+/// API rather than assuming a name.
+pub fn from_scancode_database_http(version: Option<&str>) -> Result<Vec<ScancodeLicense>> {
+    let reference = match version {
+        Some(version) => version.to_string(),
+        None => default_branch()?,
+    };
+    let docs_url = format!("{RAW_BASE_URL}/{reference}/docs");
+
+    let index: Vec<IndexEntry> = serde_yaml::from_str(
+        &ureq::get(&format!("{docs_url}/index.yml"))
+            .call()?
+            .into_string()?,
+    )?;
+
+    let mut licenses = Vec::with_capacity(index.len());
+
+    for entry in index {
+        let yaml = ureq::get(&format!("{docs_url}/{}.yml", entry.license_key))
+            .call()?
+            .into_string()?;
+
+        // Not every license carries a separate text file (e.g. some unstated licenses), so a
+        // missing `.LICENSE` file is not an error — but any other failure (rate limiting,
+        // transport errors, ...) must not be mistaken for one, or it would silently produce a
+        // license with empty text.
+        let text = match ureq::get(&format!("{docs_url}/{}.LICENSE", entry.license_key)).call() {
+            Ok(response) => response.into_string()?,
+            Err(ureq::Error::Status(404, _)) => String::new(),
+            Err(err) => return Err(err.into()),
+        };
+
+        licenses.push(ScancodeLicense::from_yaml_str(&yaml, text)?);
+    }
+
+    Ok(licenses)
+}
+
+/// Entry in the license database's `index.yml` manifest, enough to locate each license's YAML
+/// metadata and text files. Each entry is keyed by `license_key`, not `key`.
+#[derive(Deserialize)]
+struct IndexEntry {
+    license_key: String,
+}
+
+/// Resolve the `scancode-licensedb` repository's actual default branch via the GitHub API.
+/// `raw.githubusercontent.com` has no alias for "the default branch", so guessing a name like
+/// `main` would silently 404 if upstream ever renames it.
+fn default_branch() -> Result<String> {
+    #[derive(Deserialize)]
+    struct Repo {
+        default_branch: String,
+    }
+
+    let repo: Repo = ureq::get("https://api.github.com/repos/nexB/scancode-licensedb")
+        .call()?
+        .into_json()?;
+
+    Ok(repo.default_branch)
+}
+
+/// The OS-conventional cache directory for imported license databases (e.g.
+/// `~/.cache/scancode-rs` on Linux), if one can be determined for the current user.
+#[must_use]
+pub fn cache_dir() -> Option<PathBuf> {
+    ProjectDirs::from("", "", "scancode-rs").map(|dirs| dirs.cache_dir().to_path_buf())
+}
+
+/// Load a set of licenses previously written by [`store_to_cache`], without touching the
+/// network.
+pub fn from_cache<P: AsRef<Path>>(path: P) -> Result<Vec<ScancodeLicense>> {
+    let bytes = read(path.as_ref()).map_err(|err| ScancodeError::Io {
+        source: err,
+        path: path.as_ref().to_path_buf(),
+    })?;
+
+    decode_cache(&bytes)
+}
+
+/// Persist `licenses` to `path`, keyed by whichever database ref the caller fetched them from,
+/// so a later [`from_cache`] call can load them without hitting the network.
+pub fn store_to_cache<P: AsRef<Path>>(licenses: &[ScancodeLicense], path: P) -> Result<()> {
+    if let Some(parent) = path.as_ref().parent() {
+        create_dir_all(parent).map_err(|err| ScancodeError::Io {
+            source: err,
+            path: parent.to_path_buf(),
+        })?;
+    }
+
+    write(path.as_ref(), encode_cache(licenses)?).map_err(|err| ScancodeError::Io {
+        source: err,
+        path: path.as_ref().to_path_buf(),
+    })
+}
+
+/// Serialize `licenses` for the on-disk cache / embedded snapshot.
+///
+/// `ScancodeLicense::text` is `#[serde(skip)]` (it isn't part of the YAML metadata, and is read
+/// from a sibling `.LICENSE` file instead), so bincode alone would silently drop it. Pair each
+/// license with its text explicitly so a round trip through the cache keeps it.
+fn encode_cache(licenses: &[ScancodeLicense]) -> Result<Vec<u8>> {
+    let entries: Vec<(&ScancodeLicense, &str)> = licenses
+        .iter()
+        .map(|license| (license, license.text.as_str()))
+        .collect();
+
+    Ok(bincode::serialize(&entries)?)
+}
+
+/// Deserialize bytes produced by [`encode_cache`] back into licenses, restoring `text`.
+fn decode_cache(bytes: &[u8]) -> Result<Vec<ScancodeLicense>> {
+    let entries: Vec<(ScancodeLicense, String)> = bincode::deserialize(bytes)?;
+
+    Ok(entries
+        .into_iter()
+        .map(|(mut license, text)| {
+            license.text = text;
+            license
+        })
+        .collect())
+}
+
+/// Zstd-compressed, bincode-serialized snapshot of the ScanCode license database, embedded into
+/// the crate at compile time so [`from_embedded_snapshot`] can load licenses with no network
+/// access at all.
+///
+/// `data/snapshot.bin.zst` is not checked in: it's hundreds of kilobytes of ScanCode license
+/// text that goes stale the moment upstream changes, so a maintainer with network access must
+/// generate it before enabling this feature, by calling [`store_to_cache`] against a pinned
+/// database ref (e.g. via [`from_scancode_database_http`]) and compressing the result with
+/// `zstd`. Enabling `embedded-snapshot` without first generating that file is a compile error
+/// rather than silently embedding an empty database.
+#[cfg(feature = "embedded-snapshot")]
+static EMBEDDED_SNAPSHOT: &[u8] = include_bytes!("../data/snapshot.bin.zst");
+
+/// Get all licenses from the snapshot embedded in this crate at compile time.
+///
+/// This loads in microseconds and never touches the network, at the cost of only reflecting the
+/// database ref the embedded snapshot was built from.
+#[cfg(feature = "embedded-snapshot")]
+pub fn from_embedded_snapshot() -> Result<Vec<ScancodeLicense>> {
+    let licenses = decode_cache(&zstd::stream::decode_all(EMBEDDED_SNAPSHOT)?)?;
+
+    if licenses.is_empty() {
+        return Err(ScancodeError::Cache(
+            "embedded license snapshot is empty; regenerate data/snapshot.bin.zst".into(),
+        ));
+    }
+
     Ok(licenses)
 }
 
@@ -2,5 +2,9 @@
 #![allow(clippy::doc_markdown)]
 //! This is a utility crate for interacting with [ScanCode Toolkit](https://github.com/nexB/scancode-toolkit).
 
+pub mod db;
+pub mod detect;
 pub mod import;
 pub mod models;
+pub mod report;
+pub mod spdx;
@@ -127,10 +127,54 @@ impl ScancodeLicense {
 
         Ok(license)
     }
+
+    /// Deserialize [Self] from an in-memory YAML string and its accompanying license text.
+    ///
+    /// Used when a license's metadata and text are fetched over HTTP rather than read from
+    /// disk, so there is no path to attach to a parse error.
+    pub(crate) fn from_yaml_str(yaml: &str, text: String) -> Result<Self> {
+        let mut license = serde_yaml::from_str::<Self>(yaml)?;
+        license.text = text;
+
+        Ok(license)
+    }
+
+    /// Build a minimal license for unit tests elsewhere in the crate, with every field defaulted
+    /// except `key` and `text`.
+    #[cfg(test)]
+    pub(crate) fn test_fixture(key: &str, text: &str) -> Self {
+        Self {
+            key: key.to_string(),
+            short_name: key.to_string(),
+            name: key.to_string(),
+            category: Category::Permissive,
+            owner: "Test".to_string(),
+            homepage_url: None,
+            notes: None,
+            is_deprecated: false,
+            spdx_license_key: None,
+            text_urls: Vec::new(),
+            osi_url: None,
+            osi_license_key: None,
+            faq_url: None,
+            other_urls: Vec::new(),
+            is_exception: false,
+            other_spdx_license_keys: Vec::new(),
+            ignorable_copyrights: Vec::new(),
+            ignorable_holders: Vec::new(),
+            ignorable_authors: Vec::new(),
+            ignorable_urls: Vec::new(),
+            ignorable_emails: Vec::new(),
+            minimum_coverage: None,
+            standard_notice: None,
+            language: None,
+            text: text.to_string(),
+        }
+    }
 }
 
 /// Different license cateogires from ScanCode.
-#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Category {
     Copyleft,
     #[serde(rename = "Copyleft Limited")]
@@ -343,6 +387,24 @@ pub enum ScancodeError {
     #[error("Error with git.")]
     Git(#[from] git2::Error),
 
+    #[error("Error with HTTP request.")]
+    Http(#[from] ureq::Error),
+
+    #[error("Error with bincode.")]
+    Bincode(#[from] Box<bincode::ErrorKind>),
+
+    #[error("Error with license cache: {0}")]
+    Cache(String),
+
+    #[error("Error parsing SPDX expression: {0}")]
+    Spdx(String),
+
+    #[error("Error with serde_json.")]
+    SerdeJson(#[from] serde_json::Error),
+
+    #[error("Error building report: {0}")]
+    Report(String),
+
     #[error("SerdeYaml error with path {path:?}.")]
     SerdeYaml {
         source: serde_yaml::Error,
@@ -0,0 +1,180 @@
+//! Generate attribution / NOTICE documents from a set of used licenses.
+//!
+//! Given a [`ScancodeLicenseDb`] and the license keys actually in use, [`build`] produces a
+//! deduplicated [`Report`] grouped by [`Category`], which can then be rendered as a plain-text
+//! NOTICE via a user-supplied template ([`render_notice`]) or as JSON ([`render_json`]), the way
+//! `cargo-about` and rust's `generate-copyright` render per-crate license metadata.
+
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+
+use serde::Serialize;
+
+use crate::db::ScancodeLicenseDb;
+use crate::models::Category;
+use crate::models::Result;
+use crate::models::ScancodeError;
+
+/// A single license in a [`Report`].
+#[derive(Debug, Serialize)]
+pub struct ReportEntry {
+    pub key: String,
+    pub name: String,
+    pub owner: String,
+    pub standard_notice: Option<String>,
+    pub text: String,
+}
+
+/// The licenses in a [`Report`] that share a [`Category`].
+#[derive(Debug, Serialize)]
+pub struct CategoryGroup {
+    pub category: Category,
+    pub licenses: Vec<ReportEntry>,
+}
+
+/// A deduplicated attribution report, grouped by [`Category`].
+#[derive(Debug, Serialize)]
+pub struct Report {
+    pub groups: Vec<CategoryGroup>,
+}
+
+/// Build an attribution report for `used_keys` out of `db`, deduplicating repeated keys and
+/// grouping the result by [`Category`].
+pub fn build(db: &ScancodeLicenseDb, used_keys: &[&str]) -> Result<Report> {
+    let mut grouped: BTreeMap<Category, Vec<ReportEntry>> = BTreeMap::new();
+    let mut seen = HashSet::new();
+
+    for key in used_keys {
+        if !seen.insert(*key) {
+            continue;
+        }
+
+        let license = db
+            .by_key(key)
+            .ok_or_else(|| ScancodeError::Report(format!("unknown license key {key:?}")))?;
+
+        grouped
+            .entry(license.category.clone())
+            .or_default()
+            .push(ReportEntry {
+                key: license.key.clone(),
+                name: license.name.clone(),
+                owner: license.owner.clone(),
+                standard_notice: license.standard_notice.clone(),
+                text: license.text.clone(),
+            });
+    }
+
+    Ok(Report {
+        groups: grouped
+            .into_iter()
+            .map(|(category, licenses)| CategoryGroup { category, licenses })
+            .collect(),
+    })
+}
+
+/// Render `report` as a plain-text NOTICE document, with one section per [`Category`] and
+/// `template` rendered once per license within it, substituting `{key}`, `{name}`, `{owner}`,
+/// `{standard_notice}` and `{text}` placeholders.
+#[must_use]
+pub fn render_notice(report: &Report, template: &str) -> String {
+    report
+        .groups
+        .iter()
+        .map(|group| render_group(group, template))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+fn render_group(group: &CategoryGroup, template: &str) -> String {
+    let header = format!("{:?}", group.category);
+    let licenses = group
+        .licenses
+        .iter()
+        .map(|entry| render_entry(template, entry))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    format!("{header}\n{licenses}")
+}
+
+fn render_entry(template: &str, entry: &ReportEntry) -> String {
+    template
+        .replace("{key}", &entry.key)
+        .replace("{name}", &entry.name)
+        .replace("{owner}", &entry.owner)
+        .replace(
+            "{standard_notice}",
+            entry.standard_notice.as_deref().unwrap_or(""),
+        )
+        .replace("{text}", &entry.text)
+}
+
+/// Render `report` as JSON.
+pub fn render_json(report: &Report) -> Result<String> {
+    Ok(serde_json::to_string_pretty(report)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::ScancodeLicense;
+
+    use super::*;
+
+    fn test_db() -> ScancodeLicenseDb {
+        let mit = ScancodeLicense::test_fixture("mit", "MIT license text.");
+
+        let mut gpl = ScancodeLicense::test_fixture("gpl-3.0", "GPL license text.");
+        gpl.category = crate::models::Category::Copyleft;
+
+        ScancodeLicenseDb::new(vec![mit, gpl])
+    }
+
+    #[test]
+    fn dedupes_repeated_keys_and_groups_by_category() {
+        let db = test_db();
+
+        let report = build(&db, &["mit", "mit", "gpl-3.0"]).unwrap();
+
+        assert_eq!(report.groups.len(), 2);
+        let total_licenses: usize = report.groups.iter().map(|group| group.licenses.len()).sum();
+        assert_eq!(total_licenses, 2);
+    }
+
+    #[test]
+    fn build_errors_on_unknown_key() {
+        let db = test_db();
+
+        assert!(build(&db, &["does-not-exist"]).is_err());
+    }
+
+    #[test]
+    fn renders_notice_with_placeholders_substituted() {
+        let db = test_db();
+        let report = build(&db, &["mit"]).unwrap();
+
+        let notice = render_notice(&report, "{key}: {name}\n{text}");
+
+        assert_eq!(notice, "Permissive\nmit: mit\nMIT license text.");
+    }
+
+    #[test]
+    fn renders_notice_with_one_section_per_category() {
+        let db = test_db();
+        let report = build(&db, &["mit", "gpl-3.0"]).unwrap();
+
+        let notice = render_notice(&report, "{key}");
+
+        assert_eq!(notice, "Copyleft\ngpl-3.0\n\nPermissive\nmit");
+    }
+
+    #[test]
+    fn renders_json() {
+        let db = test_db();
+        let report = build(&db, &["mit"]).unwrap();
+
+        let json = render_json(&report).unwrap();
+
+        assert!(json.contains("\"key\": \"mit\""));
+    }
+}
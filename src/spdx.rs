@@ -0,0 +1,342 @@
+//! Resolve and validate SPDX license expressions against a [`ScancodeLicenseDb`].
+//!
+//! An expression such as `Apache-2.0 WITH LLVM-exception OR (MIT AND GPL-3.0)` is parsed into its
+//! `AND`/`OR`/`WITH` tree and each identifier is resolved back to the [`ScancodeLicense`] whose
+//! `spdx_license_key`/`other_spdx_license_keys` matches it, the same way `cargo-bundle-licenses`
+//! resolves a `WITH` operand against the licenses ScanCode flags as `is_exception`.
+
+use crate::db::ScancodeLicenseDb;
+use crate::models::Result;
+use crate::models::ScancodeError;
+use crate::models::ScancodeLicense;
+
+/// A parsed SPDX license expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expression {
+    /// A single license or exception identifier.
+    License(String),
+
+    /// `<license> WITH <exception>`, treated as a single unit.
+    WithException(String, String),
+
+    /// `<left> AND <right>`.
+    And(Box<Expression>, Box<Expression>),
+
+    /// `<left> OR <right>`.
+    Or(Box<Expression>, Box<Expression>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    LParen,
+    RParen,
+    And,
+    Or,
+    With,
+    Ident(String),
+}
+
+fn tokenize(expr: &str) -> Vec<Token> {
+    expr.replace('(', " ( ")
+        .replace(')', " ) ")
+        .split_whitespace()
+        .map(|word| match word {
+            "(" => Token::LParen,
+            ")" => Token::RParen,
+            "AND" => Token::And,
+            "OR" => Token::Or,
+            "WITH" => Token::With,
+            other => Token::Ident(other.to_string()),
+        })
+        .collect()
+}
+
+/// Parse a full SPDX license expression into its `AND`/`OR`/`WITH` tree.
+pub fn parse(expr: &str) -> Result<Expression> {
+    let tokens = tokenize(expr);
+    let mut parser = Parser { tokens: &tokens, position: 0 };
+    let expression = parser.parse_or()?;
+
+    if parser.position != parser.tokens.len() {
+        return Err(ScancodeError::Spdx(format!(
+            "unexpected trailing tokens in expression {expr:?}"
+        )));
+    }
+
+    Ok(expression)
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn advance(&mut self) -> Option<&Token> {
+        let token = self.tokens.get(self.position);
+        self.position += 1;
+        token
+    }
+
+    fn parse_or(&mut self) -> Result<Expression> {
+        let mut expression = self.parse_and()?;
+
+        while self.peek() == Some(&Token::Or) {
+            self.advance();
+            expression = Expression::Or(Box::new(expression), Box::new(self.parse_and()?));
+        }
+
+        Ok(expression)
+    }
+
+    fn parse_and(&mut self) -> Result<Expression> {
+        let mut expression = self.parse_with()?;
+
+        while self.peek() == Some(&Token::And) {
+            self.advance();
+            expression = Expression::And(Box::new(expression), Box::new(self.parse_with()?));
+        }
+
+        Ok(expression)
+    }
+
+    fn parse_with(&mut self) -> Result<Expression> {
+        let expression = self.parse_primary()?;
+
+        if self.peek() == Some(&Token::With) {
+            self.advance();
+
+            let Expression::License(license) = expression else {
+                return Err(ScancodeError::Spdx(
+                    "WITH must follow a single license identifier".into(),
+                ));
+            };
+
+            return match self.advance() {
+                Some(Token::Ident(exception)) => {
+                    Ok(Expression::WithException(license, exception.clone()))
+                }
+                _ => Err(ScancodeError::Spdx(
+                    "expected an exception identifier after WITH".into(),
+                )),
+            };
+        }
+
+        Ok(expression)
+    }
+
+    fn parse_primary(&mut self) -> Result<Expression> {
+        match self.advance() {
+            Some(Token::Ident(id)) => Ok(Expression::License(id.clone())),
+            Some(Token::LParen) => {
+                let expression = self.parse_or()?;
+
+                match self.advance() {
+                    Some(Token::RParen) => Ok(expression),
+                    _ => Err(ScancodeError::Spdx("unbalanced parentheses".into())),
+                }
+            }
+            other => Err(ScancodeError::Spdx(format!(
+                "expected a license identifier or '(', found {other:?}"
+            ))),
+        }
+    }
+}
+
+/// The outcome of resolving an [`Expression`] against a [`ScancodeLicenseDb`].
+#[derive(Debug, Default)]
+pub struct Resolution<'a> {
+    /// Licenses resolved from the expression, deduplicated by key.
+    pub licenses: Vec<&'a ScancodeLicense>,
+
+    /// Identifiers referenced by the expression that have no corresponding ScanCode license.
+    pub unresolved: Vec<String>,
+}
+
+/// Parse `expr` and resolve each license/exception identifier in it against `db`.
+pub fn resolve<'a>(expr: &str, db: &'a ScancodeLicenseDb) -> Result<Resolution<'a>> {
+    let expression = parse(expr)?;
+    let mut resolution = Resolution::default();
+    collect(&expression, db, &mut resolution);
+
+    Ok(resolution)
+}
+
+fn collect<'a>(expression: &Expression, db: &'a ScancodeLicenseDb, resolution: &mut Resolution<'a>) {
+    match expression {
+        Expression::License(id) => resolve_one(id, db, resolution, |_| true),
+        Expression::WithException(license, exception) => {
+            resolve_one(license, db, resolution, |_| true);
+            resolve_one(exception, db, resolution, |license| license.is_exception);
+        }
+        Expression::And(lhs, rhs) | Expression::Or(lhs, rhs) => {
+            collect(lhs, db, resolution);
+            collect(rhs, db, resolution);
+        }
+    }
+}
+
+/// Resolve `id` against `db`, only accepting matches for which `accept` holds (used to constrain
+/// a `WITH` operand to licenses where `is_exception` is true, rather than any SPDX identifier).
+fn resolve_one<'a>(
+    id: &str,
+    db: &'a ScancodeLicenseDb,
+    resolution: &mut Resolution<'a>,
+    accept: impl Fn(&ScancodeLicense) -> bool,
+) {
+    match db.by_spdx(id).filter(|license| accept(license)) {
+        Some(license) => {
+            if !resolution.licenses.iter().any(|existing| existing.key == license.key) {
+                resolution.licenses.push(license);
+            }
+        }
+        None => resolution.unresolved.push(id.to_string()),
+    }
+}
+
+/// Check whether `expr` is fully covered by `allowed` ScanCode license keys, usable for
+/// `cargo-deny`-style allow/deny policies.
+///
+/// `AND` requires both sides to be allowed, `OR` requires either side, and a `WITH` exception is
+/// satisfied whenever its base license is allowed.
+pub fn satisfies(expr: &str, db: &ScancodeLicenseDb, allowed: &[&str]) -> Result<bool> {
+    Ok(evaluate(&parse(expr)?, db, allowed))
+}
+
+fn evaluate(expression: &Expression, db: &ScancodeLicenseDb, allowed: &[&str]) -> bool {
+    match expression {
+        Expression::License(id) | Expression::WithException(id, _) => {
+            is_allowed(id, db, allowed)
+        }
+        Expression::And(lhs, rhs) => evaluate(lhs, db, allowed) && evaluate(rhs, db, allowed),
+        Expression::Or(lhs, rhs) => evaluate(lhs, db, allowed) || evaluate(rhs, db, allowed),
+    }
+}
+
+fn is_allowed(id: &str, db: &ScancodeLicenseDb, allowed: &[&str]) -> bool {
+    db.by_spdx(id)
+        .is_some_and(|license| allowed.contains(&license.key.as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::ScancodeLicense;
+
+    use super::*;
+
+    fn test_db() -> ScancodeLicenseDb {
+        let mut mit = ScancodeLicense::test_fixture("mit", "");
+        mit.spdx_license_key = Some("MIT".to_string());
+
+        let mut apache = ScancodeLicense::test_fixture("apache-2.0", "");
+        apache.spdx_license_key = Some("Apache-2.0".to_string());
+
+        let mut gpl = ScancodeLicense::test_fixture("gpl-3.0", "");
+        gpl.spdx_license_key = Some("GPL-3.0".to_string());
+
+        let mut exception = ScancodeLicense::test_fixture("llvm-exception", "");
+        exception.spdx_license_key = Some("LLVM-exception".to_string());
+        exception.is_exception = true;
+
+        ScancodeLicenseDb::new(vec![mit, apache, gpl, exception])
+    }
+
+    #[test]
+    fn parses_and_before_or() {
+        // AND binds tighter than OR, so this is `MIT OR (Apache-2.0 AND GPL-3.0)`.
+        let expression = parse("MIT OR Apache-2.0 AND GPL-3.0").unwrap();
+
+        assert_eq!(
+            expression,
+            Expression::Or(
+                Box::new(Expression::License("MIT".to_string())),
+                Box::new(Expression::And(
+                    Box::new(Expression::License("Apache-2.0".to_string())),
+                    Box::new(Expression::License("GPL-3.0".to_string())),
+                )),
+            )
+        );
+    }
+
+    #[test]
+    fn parentheses_override_precedence() {
+        let expression = parse("(MIT OR Apache-2.0) AND GPL-3.0").unwrap();
+
+        assert_eq!(
+            expression,
+            Expression::And(
+                Box::new(Expression::Or(
+                    Box::new(Expression::License("MIT".to_string())),
+                    Box::new(Expression::License("Apache-2.0".to_string())),
+                )),
+                Box::new(Expression::License("GPL-3.0".to_string())),
+            )
+        );
+    }
+
+    #[test]
+    fn parses_with_exception_as_a_unit() {
+        let expression = parse("Apache-2.0 WITH LLVM-exception").unwrap();
+
+        assert_eq!(
+            expression,
+            Expression::WithException("Apache-2.0".to_string(), "LLVM-exception".to_string())
+        );
+    }
+
+    #[test]
+    fn rejects_with_without_an_exception() {
+        assert!(parse("Apache-2.0 WITH").is_err());
+    }
+
+    #[test]
+    fn rejects_with_after_a_compound_expression() {
+        assert!(parse("(MIT OR Apache-2.0) WITH LLVM-exception").is_err());
+    }
+
+    #[test]
+    fn rejects_trailing_tokens() {
+        assert!(parse("MIT Apache-2.0").is_err());
+    }
+
+    #[test]
+    fn rejects_unbalanced_parentheses() {
+        assert!(parse("(MIT OR Apache-2.0").is_err());
+    }
+
+    #[test]
+    fn resolves_known_and_unknown_identifiers() {
+        let db = test_db();
+
+        let resolution = resolve("MIT OR Unknown-License", &db).unwrap();
+
+        assert_eq!(resolution.licenses.len(), 1);
+        assert_eq!(resolution.licenses[0].key, "mit");
+        assert_eq!(resolution.unresolved, vec!["Unknown-License".to_string()]);
+    }
+
+    #[test]
+    fn with_operand_must_resolve_to_an_exception() {
+        let db = test_db();
+
+        let resolution = resolve("Apache-2.0 WITH GPL-3.0", &db).unwrap();
+
+        assert_eq!(resolution.licenses.len(), 1);
+        assert_eq!(resolution.licenses[0].key, "apache-2.0");
+        assert_eq!(resolution.unresolved, vec!["GPL-3.0".to_string()]);
+    }
+
+    #[test]
+    fn satisfies_respects_and_or_with() {
+        let db = test_db();
+
+        assert!(satisfies("MIT OR GPL-3.0", &db, &["mit"]).unwrap());
+        assert!(!satisfies("MIT AND GPL-3.0", &db, &["mit"]).unwrap());
+        assert!(satisfies("Apache-2.0 WITH LLVM-exception", &db, &["apache-2.0"]).unwrap());
+        assert!(!satisfies("GPL-3.0", &db, &["mit", "apache-2.0"]).unwrap());
+    }
+}